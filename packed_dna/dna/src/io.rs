@@ -0,0 +1,303 @@
+//! Streaming FASTA/FASTQ parsing built on `nom` parser combinators.
+//!
+//! This module reads multi-record FASTA (`>header` / sequence lines) and
+//! FASTQ (`@header` / sequence / `+` / quality) text into `(header,
+//! [PackedDna](crate::packed::PackedDna))` pairs. Sequence lines that are
+//! wrapped across several physical lines are joined before being packed. A
+//! FASTQ quality block is matched one line per sequence line that was
+//! joined, each required to have the same length as its sequence line —
+//! since quality text is otherwise unconstrained (it may itself start
+//! with `>`/`@`/`+`), length is the only reliable way to tell a short or
+//! malformed quality block from a legitimate one instead of silently
+//! consuming the next record's header. Each joined sequence line's
+//! position is carried along so a malformed base can be reported against
+//! the physical line that contains it.
+
+use crate::packed::{PackedDna, ParsePackedNucsError};
+use nom::{
+    bytes::complete::is_not, character::complete::char, combinator::rest, sequence::preceded,
+    IResult,
+};
+use std::str::FromStr;
+
+/// A single FASTA/FASTQ record: its header text and packed sequence.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Record {
+    /// Header text, not including the leading `>`/`@` sigil.
+    pub header: String,
+    /// The record's sequence, packed 2 bits per base.
+    pub dna: PackedDna,
+}
+
+/// An error that can occur while parsing a FASTA/FASTQ file into `Record`s.
+#[derive(Debug, thiserror::Error)]
+pub enum RecordsParseError {
+    /// A line that should have opened a new record (`>...` or `@...`) did
+    /// not match the expected grammar.
+    #[error("expected a '>' or '@' header at line {line}")]
+    MissingHeader {
+        /// 1-based line number where the header was expected.
+        line: usize,
+    },
+    /// The sequence body of the record contained a character that cannot
+    /// be packed into a nucleotide.
+    #[error("invalid nucleotide in record at line {line}: {source}")]
+    InvalidSequence {
+        /// 1-based line number of the physical sequence line that
+        /// contains the offending character, falling back to the
+        /// record's header line if it can't be pinpointed.
+        line: usize,
+        /// The underlying packing error.
+        #[source]
+        source: ParsePackedNucsError<char>,
+    },
+    /// A FASTQ quality line didn't have the same length as the sequence
+    /// line it corresponds to (or was missing entirely).
+    #[error("quality line at line {line} has length {found}, expected {expected} to match its sequence line")]
+    MismatchedQualityLength {
+        /// 1-based line number of the offending quality line, or of the
+        /// `+` separator if the input ended before this quality line.
+        line: usize,
+        /// The corresponding sequence line's length.
+        expected: usize,
+        /// The quality line's actual length, or 0 if there was no line
+        /// left to read.
+        found: usize,
+    },
+}
+
+/// Parses a single header line (`>foo` or `@foo`), returning the text after
+/// the sigil.
+fn header_line(input: &str) -> IResult<&str, &str> {
+    preceded(nom::branch::alt((char('>'), char('@'))), rest)(input)
+}
+
+/// Parses a `+`-prefixed FASTQ separator line, returning the text after it
+/// (an optional repeat of the header, which is discarded).
+fn plus_line(input: &str) -> IResult<&str, &str> {
+    preceded(char('+'), rest)(input)
+}
+
+/// Parses a sequence or quality line: anything that isn't itself a header or
+/// separator line.
+fn body_line(input: &str) -> IResult<&str, &str> {
+    is_not(">@+")(input)
+}
+
+/// Parses every FASTA/FASTQ record out of `input`, packing each sequence
+/// body with [`PackedDna::from_str`].
+///
+/// Sequence lines are joined across wraps until the next header or EOF. A
+/// FASTQ `+`/quality block is recognized and, if present, exactly as many
+/// quality lines as sequence lines are matched against it, each required
+/// to be the same length as its sequence line. A record whose sequence
+/// body is empty packs to an empty `PackedDna` rather than failing.
+///
+/// # Errors
+///
+/// Returns [`RecordsParseError::MissingHeader`] if a non-blank line outside
+/// a record doesn't start a new one,
+/// [`RecordsParseError::InvalidSequence`] if a record's joined sequence
+/// contains an unsupported base, or
+/// [`RecordsParseError::MismatchedQualityLength`] if a FASTQ quality line's
+/// length doesn't match its sequence line's.
+pub fn parse_records(input: &str) -> Result<Vec<Record>, RecordsParseError> {
+    let mut records = Vec::new();
+    let mut lines = input.lines().enumerate().peekable();
+
+    while let Some((line_no, line)) = lines.next() {
+        if line.is_empty() {
+            continue;
+        }
+
+        let header = header_line(line)
+            .map(|(_, header)| header.to_string())
+            .map_err(|_| RecordsParseError::MissingHeader { line: line_no + 1 })?;
+        let is_fastq = line.starts_with('@');
+
+        let mut sequence = String::new();
+        let mut sequence_lines = Vec::new();
+        while let Some(&(next_no, next)) = lines.peek() {
+            if body_line(next).is_ok() && !next.is_empty() {
+                sequence.push_str(next);
+                sequence_lines.push((next_no, next));
+                lines.next();
+            } else {
+                break;
+            }
+        }
+
+        // A FASTQ record is followed by a `+` separator and one quality
+        // line per sequence line already consumed, both discarded. A
+        // quality line's content is unconstrained (it can itself start
+        // with '>'/'@'/'+'), so the only reliable way to tell a short
+        // quality block from a legitimate one is that each quality line
+        // must match the length of the sequence line it covers.
+        if is_fastq {
+            if let Some(&(plus_line_no, next)) = lines.peek() {
+                if plus_line(next).is_ok() {
+                    lines.next();
+                    for &(_, seq_text) in &sequence_lines {
+                        match lines.peek() {
+                            Some(&(_, quality_text)) if quality_text.len() == seq_text.len() => {
+                                lines.next();
+                            }
+                            Some(&(found_line_no, quality_text)) => {
+                                return Err(RecordsParseError::MismatchedQualityLength {
+                                    line: found_line_no + 1,
+                                    expected: seq_text.len(),
+                                    found: quality_text.len(),
+                                });
+                            }
+                            None => {
+                                return Err(RecordsParseError::MismatchedQualityLength {
+                                    line: plus_line_no + 1,
+                                    expected: seq_text.len(),
+                                    found: 0,
+                                });
+                            }
+                        }
+                    }
+                }
+            }
+        }
+
+        let dna = PackedDna::from_str(&sequence).map_err(|source| {
+            let bad_char = source.invalid_char().to_ascii_uppercase();
+            let line = sequence_lines
+                .iter()
+                .find(|(_, text)| text.to_ascii_uppercase().contains(bad_char))
+                .map_or(line_no, |(bad_line, _)| *bad_line);
+            RecordsParseError::InvalidSequence {
+                line: line + 1,
+                source,
+            }
+        })?;
+        records.push(Record { header, dna });
+    }
+
+    Ok(records)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::Nuc;
+
+    #[test]
+    fn parses_single_fasta_record() {
+        let input = ">seq1\nACGT\n";
+        let records = parse_records(input).unwrap();
+        assert_eq!(records.len(), 1);
+        assert_eq!(records[0].header, "seq1");
+        assert_eq!(records[0].dna.get(0).unwrap(), Nuc::A);
+        assert_eq!(records[0].dna.len(), 4);
+    }
+
+    #[test]
+    fn joins_wrapped_sequence_lines() {
+        let input = ">seq1\nAC\nGT\nAA\n";
+        let records = parse_records(input).unwrap();
+        assert_eq!(records.len(), 1);
+        assert_eq!(records[0].dna.len(), 6);
+    }
+
+    #[test]
+    fn parses_multiple_records() {
+        let input = ">seq1\nACGT\n>seq2\nTTTT\n";
+        let records = parse_records(input).unwrap();
+        assert_eq!(records.len(), 2);
+        assert_eq!(records[0].header, "seq1");
+        assert_eq!(records[1].header, "seq2");
+        assert_eq!(records[1].dna.len(), 4);
+    }
+
+    #[test]
+    fn parses_fastq_record_and_skips_quality() {
+        let input = "@seq1\nACGT\n+\nIIII\n@seq2\nTTAA\n+seq2\nIIII\n";
+        let records = parse_records(input).unwrap();
+        assert_eq!(records.len(), 2);
+        assert_eq!(records[0].header, "seq1");
+        assert_eq!(records[1].header, "seq2");
+    }
+
+    #[test]
+    fn empty_sequence_packs_to_empty_dna() {
+        let input = ">empty\n>seq2\nAC\n";
+        let records = parse_records(input).unwrap();
+        assert_eq!(records[0].dna.len(), 0);
+        assert_eq!(records[1].dna.len(), 2);
+    }
+
+    #[test]
+    fn reports_offending_line_on_invalid_base() {
+        let input = ">seq1\nACGT\n>seq2\nACXT\n";
+        let err = parse_records(input).unwrap_err();
+        match err {
+            RecordsParseError::InvalidSequence { line, .. } => assert_eq!(line, 4),
+            other => panic!("unexpected error: {:?}", other),
+        }
+    }
+
+    #[test]
+    fn reports_offending_line_among_wrapped_sequence_lines() {
+        let input = ">seq1\nAC\nGX\nAA\n";
+        let err = parse_records(input).unwrap_err();
+        match err {
+            RecordsParseError::InvalidSequence { line, .. } => assert_eq!(line, 3),
+            other => panic!("unexpected error: {:?}", other),
+        }
+    }
+
+    #[test]
+    fn skips_one_quality_line_per_wrapped_sequence_line() {
+        let input = "@seq1\nAC\nGT\nAA\n+\nII\nII\nII\n@seq2\nTTTT\n";
+        let records = parse_records(input).unwrap();
+        assert_eq!(records.len(), 2);
+        assert_eq!(records[0].header, "seq1");
+        assert_eq!(records[0].dna.len(), 6);
+        assert_eq!(records[1].header, "seq2");
+        assert_eq!(records[1].dna.len(), 4);
+    }
+
+    #[test]
+    fn quality_line_starting_with_sigil_is_not_mistaken_for_a_new_record() {
+        let input = "@seq1\nAC\nGT\n+\n@@\n@@\n@seq2\nTTTT\n";
+        let records = parse_records(input).unwrap();
+        assert_eq!(records.len(), 2);
+        assert_eq!(records[0].header, "seq1");
+        assert_eq!(records[0].dna.len(), 4);
+        assert_eq!(records[1].header, "seq2");
+        assert_eq!(records[1].dna.len(), 4);
+    }
+
+    #[test]
+    fn short_quality_block_reports_a_clear_error_instead_of_corrupting_parsing() {
+        // only 1 quality line for 2 sequence lines; the next line is a
+        // real record's header, which must not be silently consumed
+        let input = "@seq1\nAC\nGT\n+\nII\n@seq2\nTTTT\n";
+        let err = parse_records(input).unwrap_err();
+        match err {
+            RecordsParseError::MismatchedQualityLength {
+                line,
+                expected,
+                found,
+            } => {
+                assert_eq!(line, 6);
+                assert_eq!(expected, 2);
+                assert_eq!(found, 5);
+            }
+            other => panic!("unexpected error: {:?}", other),
+        }
+    }
+
+    #[test]
+    fn reports_offending_line_on_invalid_base_case_insensitive() {
+        let input = ">seq1\nACGT\n>seq2\nacxt\n";
+        let err = parse_records(input).unwrap_err();
+        match err {
+            RecordsParseError::InvalidSequence { line, .. } => assert_eq!(line, 4),
+            other => panic!("unexpected error: {:?}", other),
+        }
+    }
+}
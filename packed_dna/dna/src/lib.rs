@@ -39,11 +39,14 @@
 //!     println!("The {}-th nucleotide is {:?}",i,pi_nuc.unwrap())
 //! }
 //! ```
-//! 
+//!
 
 #![warn(missing_docs)]
 use std::{convert::TryFrom, fmt::Display, str::FromStr};
 
+/// Streaming FASTA/FASTQ parsing into `PackedDna` records.
+pub mod io;
+
 /// This module provides a PackedDna struct,
 /// a case insensitive `FromStr` implementation,
 /// a `FromIterator` implementation,
@@ -59,6 +62,19 @@ pub mod packed {
     pub struct PackedDna {
         nucs_vec: Vec<u8>,
         length: usize,
+        mode: PackingMode,
+    }
+
+    /// Which bit width `PackedDna::nucs_vec` is packed at.
+    #[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+    enum PackingMode {
+        /// 2 bits per base, 4 bases per byte. Only plain A/C/G/T input can
+        /// use this mode; it is the default, memory-optimal representation.
+        TwoBit,
+        /// 4 bits per symbol, 2 symbols per byte. Used by
+        /// [`PackedDna::from_str_extended`] to additionally support IUPAC
+        /// ambiguity codes.
+        FourBit,
     }
 
     /// An error that can occur when parsing a nucleotide.
@@ -66,6 +82,13 @@ pub mod packed {
     #[error("failed to parse nucleotide from {0}")]
     pub struct ParsePackedNucsError<T: Display>(T);
 
+    impl<T: Display> ParsePackedNucsError<T> {
+        /// The character (or other input unit) that failed to parse.
+        pub(crate) fn invalid_char(&self) -> &T {
+            &self.0
+        }
+    }
+
     /// 2. A FromStr implementation
     impl FromStr for PackedDna {
         type Err = ParsePackedNucsError<char>;
@@ -127,6 +150,7 @@ pub mod packed {
             Ok(PackedDna {
                 nucs_vec: packed_nucs,
                 length: len,
+                mode: PackingMode::TwoBit,
             })
         }
     }
@@ -176,6 +200,7 @@ pub mod packed {
             PackedDna {
                 nucs_vec: packed_nucs,
                 length: len,
+                mode: PackingMode::TwoBit,
             }
         }
     }
@@ -185,6 +210,13 @@ pub mod packed {
     #[error("failed to get nucleotide from {0}")]
     pub struct GetPackedNucsError(usize);
 
+    /// An error returned by an operation that only supports the default
+    /// 2-bit packing when called on a `PackedDna` built with
+    /// [`PackedDna::from_str_extended`].
+    #[derive(Debug, thiserror::Error)]
+    #[error("only supported for the default 2-bit packing; use get_symbol on sequences built with from_str_extended")]
+    pub struct WrongPackingModeError;
+
     /// 4. A `fn get(&self, idx: usize) -> Nuc` getter for a particular nucleotide
     impl PackedDna {
         /// This function get a particular nucleotide with index idx in the PackedDna struct
@@ -194,8 +226,12 @@ pub mod packed {
         /// when the given index in out of range,
         /// or the retrieved value cannot be converted into a Nuc type,
         /// the function return GetPackedNucsError
+        ///
+        /// This only supports the default 2-bit packing; call
+        /// [`PackedDna::get_symbol`] on a sequence built with
+        /// [`PackedDna::from_str_extended`] instead.
         pub fn get(&self, idx: usize) -> Result<Nuc, GetPackedNucsError> {
-            if idx >= self.length {
+            if idx >= self.length || self.mode != PackingMode::TwoBit {
                 return Err(GetPackedNucsError(idx));
             }
 
@@ -223,6 +259,67 @@ pub mod packed {
             }
         }
 
+        /// This function counts the occurrences of each nucleotide using word-level bit tricks
+        /// # Input: Self(PackedDna struct)
+        /// # Output: `[usize; 4]`, indexed by `Nuc as usize` (A, C, G, T)
+        ///
+        /// Each full packed `u8` is processed in one pass: the four 2-bit
+        /// codes it holds are tested in parallel by combining `byte` and
+        /// `!byte` (shifted so a code's high bit lines up with its low bit)
+        /// and masking with `0x55`, then popcounted. The trailing partial
+        /// unit, if any, is masked down to only its real groups before the
+        /// same formulas are applied, so padding bits are never mistaken
+        /// for `A`.
+        ///
+        /// # Errors
+        ///
+        /// The bit tricks above assume the default 2-bit packing. Returns
+        /// [`WrongPackingModeError`] if `self` was built with
+        /// [`PackedDna::from_str_extended`] (its 4-bit-per-symbol layout
+        /// isn't a plain `Nuc` per position) — use
+        /// [`PackedDna::get_symbol`] instead for those sequences.
+        pub fn counts(&self) -> Result<[usize; 4], WrongPackingModeError> {
+            if self.mode != PackingMode::TwoBit {
+                return Err(WrongPackingModeError);
+            }
+
+            let mut counts = [0usize; 4];
+            let max_full_unit = self.length / 4;
+
+            for &byte in &self.nucs_vec[..max_full_unit] {
+                add_unit_counts(byte, 0x55, &mut counts);
+            }
+
+            let remainder = self.length % 4;
+            if remainder != 0 {
+                let byte = self.nucs_vec[max_full_unit];
+                // a partial unit's `remainder` codes occupy its low
+                // `2 * remainder` bits; the rest are unused padding
+                let valid_groups_mask = (1u8 << (2 * remainder)) - 1;
+                add_unit_counts(byte, valid_groups_mask & 0x55, &mut counts);
+            }
+
+            Ok(counts)
+        }
+
+        /// This function returns an iterator over the nucleotides in the sequence
+        /// # Input: Self(PackedDna struct)
+        /// # Output: an iterator yielding `Nuc` in order
+        ///
+        /// # Errors
+        ///
+        /// Returns [`WrongPackingModeError`] if `self` was built with
+        /// [`PackedDna::from_str_extended`]: an IUPAC ambiguity code doesn't
+        /// map to a single `Nuc`, so that mode's sequences can't be
+        /// iterated this way — use [`PackedDna::get_symbol`] instead.
+        pub fn iter(&self) -> Result<Nucs<'_>, WrongPackingModeError> {
+            if self.mode != PackingMode::TwoBit {
+                return Err(WrongPackingModeError);
+            }
+
+            Ok(Nucs { dna: self, idx: 0 })
+        }
+
         /// This function get the length of the sequence stored in PackedDna struct
         /// # Input: Self(PackedDna struct)
         /// # Output: length(usize)
@@ -234,7 +331,7 @@ pub mod packed {
         /// # Input: Self(PackedDna struct)
         /// # Output: bool
         pub fn is_empty(&self) -> bool {
-            self.length != 0
+            self.length == 0
         }
 
         /// This function get the length of u8 vector stored in PackedDna struct
@@ -247,6 +344,423 @@ pub mod packed {
             }
             vec_new
         }
+
+        /// This function serializes the struct into a self-describing byte frame
+        /// # Input: Self(PackedDna struct)
+        /// # Output: a LEB128 varint encoding of `length`, followed by the raw `nucs_vec` payload
+        ///
+        /// The varint uses 7 data bits per byte with the high bit as a
+        /// continuation flag, so the frame can be read back without knowing
+        /// its size in advance.
+        ///
+        /// The frame does not record the packing mode, and `from_bytes`
+        /// always rebuilds a 2-bit-packed sequence, so a sequence built with
+        /// [`PackedDna::from_str_extended`] does not round-trip.
+        pub fn to_bytes(&self) -> Vec<u8> {
+            let mut bytes: Vec<u8> = Vec::new();
+            let mut remaining = self.length as u64;
+
+            loop {
+                let mut byte = (remaining & 0x7f) as u8;
+                remaining >>= 7;
+                if remaining != 0 {
+                    byte |= 0x80;
+                }
+                bytes.push(byte);
+                if remaining == 0 {
+                    break;
+                }
+            }
+
+            bytes.extend_from_slice(&self.nucs_vec);
+            bytes
+        }
+
+        /// This function reconstructs a PackedDna from the frame produced by `to_bytes`
+        /// # Input: a slice of bytes
+        /// # Output: Result
+        /// # Error
+        /// when the varint length prefix is truncated, too long to fit a
+        /// 64-bit length, or the payload that follows it is not exactly
+        /// `ceil(length/4)` bytes long, the function returns FromBytesError
+        ///
+        /// The payload is always read as the default 2-bit packing.
+        pub fn from_bytes(bytes: &[u8]) -> Result<Self, FromBytesError> {
+            let mut length: u64 = 0;
+            let mut shift: u32 = 0;
+            let mut idx = 0;
+
+            loop {
+                if shift >= 64 {
+                    return Err(FromBytesError::VarintTooLong);
+                }
+
+                let byte = *bytes.get(idx).ok_or(FromBytesError::TruncatedLength)?;
+                idx += 1;
+                length |= ((byte & 0x7f) as u64) << shift;
+                if byte & 0x80 == 0 {
+                    break;
+                }
+                shift += 7;
+            }
+
+            let length = length as usize;
+            let expected_payload_len = length.div_ceil(4);
+            let payload = &bytes[idx..];
+
+            if payload.len() != expected_payload_len {
+                return Err(FromBytesError::WrongPayloadLen {
+                    expected: expected_payload_len,
+                    found: payload.len(),
+                });
+            }
+
+            Ok(PackedDna {
+                nucs_vec: payload.to_vec(),
+                length,
+                mode: PackingMode::TwoBit,
+            })
+        }
+
+        /// This function encodes the `to_bytes` frame as standard base64 text
+        /// # Input: Self(PackedDna struct)
+        /// # Output: a base64 string, padded with `=` to a multiple of 4 characters
+        ///
+        /// The length-prefixed byte frame is what gets encoded, so the
+        /// trailing partial payload byte is reconstructed correctly on the
+        /// way back instead of being read as spurious `A` nucleotides.
+        pub fn to_base64(&self) -> String {
+            let bytes = self.to_bytes();
+            let mut out = String::with_capacity(bytes.len().div_ceil(3) * 4);
+
+            for chunk in bytes.chunks(3) {
+                let b0 = chunk[0];
+                let b1 = *chunk.get(1).unwrap_or(&0);
+                let b2 = *chunk.get(2).unwrap_or(&0);
+                let group = (b0 as u32) << 16 | (b1 as u32) << 8 | b2 as u32;
+
+                out.push(BASE64_ALPHABET[(group >> 18 & 0x3f) as usize] as char);
+                out.push(BASE64_ALPHABET[(group >> 12 & 0x3f) as usize] as char);
+                out.push(if chunk.len() > 1 {
+                    BASE64_ALPHABET[(group >> 6 & 0x3f) as usize] as char
+                } else {
+                    '='
+                });
+                out.push(if chunk.len() > 2 {
+                    BASE64_ALPHABET[(group & 0x3f) as usize] as char
+                } else {
+                    '='
+                });
+            }
+
+            out
+        }
+
+        /// This function decodes base64 text produced by `to_base64` back into a PackedDna
+        /// # Input: a base64-encoded string slice
+        /// # Output: Result
+        /// # Error
+        /// when the string contains a character outside the base64 alphabet,
+        /// is not padded to a multiple of 4 characters, or the recovered
+        /// byte frame fails `from_bytes`, the function returns FromBase64Error
+        pub fn from_base64(s: &str) -> Result<Self, FromBase64Error> {
+            let chars: Vec<char> = s.chars().collect();
+            if chars.is_empty() || !chars.len().is_multiple_of(4) {
+                return Err(FromBase64Error::InvalidPadding);
+            }
+
+            let mut bytes = Vec::with_capacity(chars.len() / 4 * 3);
+            for quantum in chars.chunks(4) {
+                let mut sextets = [0u32; 4];
+                let mut pad_count = 0usize;
+
+                for (i, &c) in quantum.iter().enumerate() {
+                    if c == '=' {
+                        pad_count += 1;
+                    } else {
+                        if pad_count != 0 {
+                            return Err(FromBase64Error::InvalidPadding);
+                        }
+                        sextets[i] = base64_sextet(c)? as u32;
+                    }
+                }
+
+                let group = sextets[0] << 18 | sextets[1] << 12 | sextets[2] << 6 | sextets[3];
+                bytes.push((group >> 16) as u8);
+                if pad_count < 2 {
+                    bytes.push((group >> 8) as u8);
+                }
+                if pad_count < 1 {
+                    bytes.push(group as u8);
+                }
+            }
+
+            Ok(PackedDna::from_bytes(&bytes)?)
+        }
+
+        /// This function packs a string slice that may contain IUPAC ambiguity codes
+        /// # Input: a slice of string
+        /// # Output: Result
+        /// # Error
+        /// when given string contains a character outside the IUPAC
+        /// nucleotide alphabet (A, C, G, T, N, R, Y, S, W, K, M, B, D, H, V),
+        /// the function returns ParseExtendedNucsError
+        ///
+        /// Unlike the plain 2-bit `FromStr` path, this widens each symbol to
+        /// 4 bits (one bit per possible A/C/G/T base) and packs two symbols
+        /// per byte, so degenerate calls like `N` or `R` can be represented.
+        /// Use [`PackedDna::get_symbol`] to read sequences built this way.
+        pub fn from_str_extended(s: &str) -> Result<Self, ParseExtendedNucsError> {
+            let upper = s.to_ascii_uppercase();
+            let mut packed_symbols: Vec<u8> = Vec::new();
+            let mut len: usize = 0;
+            let mut symbols_unit: u8 = 0;
+
+            for c in upper.chars() {
+                if len.is_multiple_of(2) && len != 0 {
+                    packed_symbols.push(symbols_unit);
+                    symbols_unit = 0;
+                }
+
+                let code = IupacCode::from_char(c).ok_or(ParseExtendedNucsError(c))?;
+                symbols_unit = (symbols_unit << 4) | code.bits();
+                len += 1;
+            }
+
+            if len != 0 {
+                packed_symbols.push(symbols_unit);
+            }
+
+            Ok(PackedDna {
+                nucs_vec: packed_symbols,
+                length: len,
+                mode: PackingMode::FourBit,
+            })
+        }
+
+        /// This function gets the symbol (exact base or IUPAC ambiguity set) at index idx
+        /// # Input: Self(PackedDna struct), index:usize
+        /// # Output: Result
+        /// # Error
+        /// when the given index is out of range, the function returns GetPackedNucsError
+        pub fn get_symbol(&self, idx: usize) -> Result<Symbol, GetPackedNucsError> {
+            if idx >= self.length {
+                return Err(GetPackedNucsError(idx));
+            }
+
+            let code = match self.mode {
+                PackingMode::TwoBit => {
+                    // `get` only fails for an out-of-range or FourBit-mode
+                    // index, neither of which can happen here
+                    return Ok(Symbol::Exact(self.get(idx).unwrap()));
+                }
+                PackingMode::FourBit => {
+                    let max_full_unit = self.length / 2;
+                    let element_idx = idx / 2;
+                    let byte = self.nucs_vec[element_idx];
+
+                    let nibble = if idx >= max_full_unit * 2 {
+                        // idx falls in the final, partial unit, whose lone
+                        // symbol sits in the low nibble
+                        byte & 0x0f
+                    } else if idx.is_multiple_of(2) {
+                        (byte >> 4) & 0x0f
+                    } else {
+                        byte & 0x0f
+                    };
+
+                    IupacCode::from_bits(nibble)
+                }
+            };
+
+            Ok(match code.as_exact() {
+                Some(nuc) => Symbol::Exact(nuc),
+                None => Symbol::Ambiguous(code),
+            })
+        }
+    }
+
+    /// An error that can occur when parsing an IUPAC-extended nucleotide sequence.
+    #[derive(Debug, thiserror::Error)]
+    #[error("failed to parse IUPAC code from {0}")]
+    pub struct ParseExtendedNucsError(char);
+
+    /// A 4-bit IUPAC ambiguity code: one bit per possible base (`A`=`0b0001`,
+    /// `C`=`0b0010`, `G`=`0b0100`, `T`=`0b1000`). A concrete base sets a
+    /// single bit; a degenerate symbol like `N` or `R` sets more than one.
+    #[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+    pub struct IupacCode(u8);
+
+    impl IupacCode {
+        /// Adenine only.
+        pub const A: IupacCode = IupacCode(0b0001);
+        /// Cytosine only.
+        pub const C: IupacCode = IupacCode(0b0010);
+        /// Guanine only.
+        pub const G: IupacCode = IupacCode(0b0100);
+        /// Thymine only.
+        pub const T: IupacCode = IupacCode(0b1000);
+        /// A or G (puRine).
+        pub const R: IupacCode = IupacCode(0b0101);
+        /// C or T (pYrimidine).
+        pub const Y: IupacCode = IupacCode(0b1010);
+        /// G or C (Strong).
+        pub const S: IupacCode = IupacCode(0b0110);
+        /// A or T (Weak).
+        pub const W: IupacCode = IupacCode(0b1001);
+        /// G or T (Keto).
+        pub const K: IupacCode = IupacCode(0b1100);
+        /// A or C (aMino).
+        pub const M: IupacCode = IupacCode(0b0011);
+        /// Not A (C, G, or T).
+        pub const B: IupacCode = IupacCode(0b1110);
+        /// Not C (A, G, or T).
+        pub const D: IupacCode = IupacCode(0b1101);
+        /// Not G (A, C, or T).
+        pub const H: IupacCode = IupacCode(0b1011);
+        /// Not T (A, C, or G).
+        pub const V: IupacCode = IupacCode(0b0111);
+        /// Any base.
+        pub const N: IupacCode = IupacCode(0b1111);
+
+        /// Maps an IUPAC letter to its ambiguity code, or `None` if `c` is
+        /// outside the IUPAC nucleotide alphabet.
+        fn from_char(c: char) -> Option<IupacCode> {
+            match c {
+                'A' => Some(Self::A),
+                'C' => Some(Self::C),
+                'G' => Some(Self::G),
+                'T' => Some(Self::T),
+                'R' => Some(Self::R),
+                'Y' => Some(Self::Y),
+                'S' => Some(Self::S),
+                'W' => Some(Self::W),
+                'K' => Some(Self::K),
+                'M' => Some(Self::M),
+                'B' => Some(Self::B),
+                'D' => Some(Self::D),
+                'H' => Some(Self::H),
+                'V' => Some(Self::V),
+                'N' => Some(Self::N),
+                _ => None,
+            }
+        }
+
+        /// Rebuilds a code from the low 4 bits of a packed nibble.
+        fn from_bits(bits: u8) -> IupacCode {
+            IupacCode(bits & 0b1111)
+        }
+
+        /// The code's raw bitmask, one bit per possible A/C/G/T base.
+        fn bits(&self) -> u8 {
+            self.0
+        }
+
+        /// Returns the single concrete `Nuc` this code stands for, or
+        /// `None` if it is ambiguous (more than one bit set).
+        pub fn as_exact(&self) -> Option<Nuc> {
+            match self.0 {
+                0b0001 => Some(Nuc::A),
+                0b0010 => Some(Nuc::C),
+                0b0100 => Some(Nuc::G),
+                0b1000 => Some(Nuc::T),
+                _ => None,
+            }
+        }
+    }
+
+    /// A nucleotide call returned by [`PackedDna::get_symbol`]: either an
+    /// unambiguous base, or an IUPAC ambiguity set standing for more than one
+    /// possible base.
+    #[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+    pub enum Symbol {
+        /// A single, unambiguous nucleotide.
+        Exact(Nuc),
+        /// An IUPAC ambiguity code.
+        Ambiguous(IupacCode),
+    }
+
+    /// Adds the nucleotide counts held by one packed unit to `counts`,
+    /// testing all four 2-bit codes in parallel under `group_mask` (a
+    /// subset of `0x55` selecting which of the unit's groups are real data).
+    fn add_unit_counts(byte: u8, group_mask: u8, counts: &mut [usize; 4]) {
+        let not_byte = !byte;
+        counts[0] += (not_byte & (not_byte >> 1) & group_mask).count_ones() as usize;
+        counts[1] += (byte & (not_byte >> 1) & group_mask).count_ones() as usize;
+        counts[2] += (not_byte & (byte >> 1) & group_mask).count_ones() as usize;
+        counts[3] += (byte & (byte >> 1) & group_mask).count_ones() as usize;
+    }
+
+    /// An iterator over the `Nuc`s held by a [`PackedDna`], returned by
+    /// [`PackedDna::iter`].
+    #[derive(Debug, Clone)]
+    pub struct Nucs<'a> {
+        dna: &'a PackedDna,
+        idx: usize,
+    }
+
+    impl<'a> Iterator for Nucs<'a> {
+        type Item = Nuc;
+
+        fn next(&mut self) -> Option<Nuc> {
+            let nuc = self.dna.get(self.idx).ok()?;
+            self.idx += 1;
+            Some(nuc)
+        }
+    }
+
+    /// The standard (non-URL-safe) base64 alphabet.
+    const BASE64_ALPHABET: &[u8; 64] =
+        b"ABCDEFGHIJKLMNOPQRSTUVWXYZabcdefghijklmnopqrstuvwxyz0123456789+/";
+
+    /// Maps a single base64 character to its 6-bit value.
+    fn base64_sextet(c: char) -> Result<u8, FromBase64Error> {
+        match c {
+            'A'..='Z' => Ok(c as u8 - b'A'),
+            'a'..='z' => Ok(c as u8 - b'a' + 26),
+            '0'..='9' => Ok(c as u8 - b'0' + 52),
+            '+' => Ok(62),
+            '/' => Ok(63),
+            _ => Err(FromBase64Error::InvalidChar(c)),
+        }
+    }
+
+    /// An error that can occur when deserializing a PackedDna from base64 text.
+    #[derive(Debug, thiserror::Error)]
+    pub enum FromBase64Error {
+        /// A character outside the standard base64 alphabet (and not `=`
+        /// padding) was encountered.
+        #[error("invalid base64 character {0:?}")]
+        InvalidChar(char),
+        /// The input was empty, not a multiple of 4 characters, or had a
+        /// non-padding character after a `=`.
+        #[error("invalid base64 padding")]
+        InvalidPadding,
+        /// The bytes recovered from decoding did not form a valid `to_bytes` frame.
+        #[error("invalid byte frame: {0}")]
+        InvalidFrame(#[from] FromBytesError),
+    }
+
+    /// An error that can occur when deserializing a PackedDna from `to_bytes`'s frame.
+    #[derive(Debug, thiserror::Error)]
+    pub enum FromBytesError {
+        /// The varint length prefix ran out of bytes before its continuation
+        /// flag was cleared.
+        #[error("truncated varint length prefix")]
+        TruncatedLength,
+        /// The varint length prefix used more continuation bytes than a
+        /// 64-bit length can ever need.
+        #[error("varint length prefix is too long to fit a 64-bit length")]
+        VarintTooLong,
+        /// The payload following the length prefix did not contain exactly
+        /// `ceil(length/4)` bytes.
+        #[error("expected {expected} payload byte(s), found {found}")]
+        WrongPayloadLen {
+            /// the payload length implied by the decoded `length`
+            expected: usize,
+            /// the number of bytes actually present after the varint
+            found: usize,
+        },
     }
 }
 
@@ -440,4 +954,137 @@ mod tests {
         let case = packed::PackedDna::from_str("").unwrap();
         assert!(case.get(4).is_err());
     }
+
+    #[test]
+    fn to_bytes_from_bytes_roundtrip() {
+        // test that a packed sequence survives a byte-frame roundtrip
+        let case = packed::PackedDna::from_str("atcgGGACgact").unwrap();
+        let bytes = case.to_bytes();
+        let decoded = packed::PackedDna::from_bytes(&bytes).unwrap();
+        assert!(decoded == case);
+
+        // test an input whose length spans multiple varint bytes
+        let long_dna: String = "A".repeat(200);
+        let case = packed::PackedDna::from_str(&long_dna).unwrap();
+        let bytes = case.to_bytes();
+        let decoded = packed::PackedDna::from_bytes(&bytes).unwrap();
+        assert!(decoded == case);
+
+        // test empty input
+        let case = packed::PackedDna::from_str("").unwrap();
+        let bytes = case.to_bytes();
+        let decoded = packed::PackedDna::from_bytes(&bytes).unwrap();
+        assert!(decoded == case);
+
+        // test truncated varint
+        assert!(packed::PackedDna::from_bytes(&[0x80]).is_err());
+
+        // test a varint with far more continuation bytes than a 64-bit
+        // length could ever need, which must error rather than overflow the shift
+        let mut malformed = vec![0x80u8; 10];
+        malformed.push(0x01);
+        assert!(packed::PackedDna::from_bytes(&malformed).is_err());
+
+        // test wrong payload length
+        let bytes = packed::PackedDna::from_str("ATCG").unwrap().to_bytes();
+        assert!(packed::PackedDna::from_bytes(&bytes[..bytes.len() - 1]).is_err());
+    }
+
+    #[test]
+    fn to_base64_from_base64_roundtrip() {
+        // test that a packed sequence survives a base64 roundtrip
+        let case = packed::PackedDna::from_str("atcgGGACgact").unwrap();
+        let text = case.to_base64();
+        let decoded = packed::PackedDna::from_base64(&text).unwrap();
+        assert!(decoded == case);
+
+        // test a length that leaves a partial final unit, to make sure the
+        // padding bits aren't read back as spurious A nucleotides
+        let case = packed::PackedDna::from_str("ATCGA").unwrap();
+        let text = case.to_base64();
+        let decoded = packed::PackedDna::from_base64(&text).unwrap();
+        assert!(decoded == case);
+        assert!(decoded.len() == 5);
+
+        // test empty input
+        let case = packed::PackedDna::from_str("").unwrap();
+        let text = case.to_base64();
+        let decoded = packed::PackedDna::from_base64(&text).unwrap();
+        assert!(decoded == case);
+
+        // test illegal character
+        assert!(packed::PackedDna::from_base64("AB!=").is_err());
+
+        // test missing padding
+        assert!(packed::PackedDna::from_base64("ABC").is_err());
+    }
+
+    #[test]
+    fn counts_packed_dna() {
+        // test a sequence spanning two full units and a partial trailing one
+        let case = packed::PackedDna::from_str("ATGCGGCTA").unwrap();
+        assert!(case.counts().unwrap() == [2, 2, 3, 2]);
+
+        // test a sequence with a partial trailing unit
+        let case = packed::PackedDna::from_str("ATGCG").unwrap();
+        assert!(case.counts().unwrap() == [1, 1, 2, 1]);
+
+        // test empty input
+        let case = packed::PackedDna::from_str("").unwrap();
+        assert!(case.counts().unwrap() == [0, 0, 0, 0]);
+    }
+
+    #[test]
+    fn iter_packed_dna() {
+        let case = packed::PackedDna::from_str("ATGCG").unwrap();
+        let collected: Vec<Nuc> = case.iter().unwrap().collect();
+        assert!(collected == vec![Nuc::A, Nuc::T, Nuc::G, Nuc::C, Nuc::G]);
+
+        let case = packed::PackedDna::from_str("").unwrap();
+        assert!(case.iter().unwrap().next().is_none());
+    }
+
+    #[test]
+    fn counts_errors_on_extended_mode() {
+        let case = packed::PackedDna::from_str_extended("ACGTN").unwrap();
+        assert!(case.counts().is_err());
+    }
+
+    #[test]
+    fn iter_errors_on_extended_mode() {
+        let case = packed::PackedDna::from_str_extended("ACGTN").unwrap();
+        assert!(case.iter().is_err());
+    }
+
+    #[test]
+    fn fromstr_extended_packed_dna() {
+        use packed::{IupacCode, Symbol};
+
+        // test a mix of concrete bases and ambiguity codes
+        let case = packed::PackedDna::from_str_extended("ACGTN").unwrap();
+        assert!(case.len() == 5);
+        assert!(case.get_symbol(0).unwrap() == Symbol::Exact(Nuc::A));
+        assert!(case.get_symbol(1).unwrap() == Symbol::Exact(Nuc::C));
+        assert!(case.get_symbol(2).unwrap() == Symbol::Exact(Nuc::G));
+        assert!(case.get_symbol(3).unwrap() == Symbol::Exact(Nuc::T));
+        assert!(case.get_symbol(4).unwrap() == Symbol::Ambiguous(IupacCode::N));
+
+        // test case insensitivity and an odd-length sequence (partial trailing unit)
+        let case = packed::PackedDna::from_str_extended("acgRY").unwrap();
+        assert!(case.len() == 5);
+        assert!(case.get_symbol(2).unwrap() == Symbol::Exact(Nuc::G));
+        assert!(case.get_symbol(3).unwrap() == Symbol::Ambiguous(IupacCode::R));
+        assert!(case.get_symbol(4).unwrap() == Symbol::Ambiguous(IupacCode::Y));
+
+        // test illegal input
+        assert!(packed::PackedDna::from_str_extended("ACGU").is_err());
+
+        // test empty input
+        let case = packed::PackedDna::from_str_extended("").unwrap();
+        assert!(case.is_empty());
+
+        // test that the plain 2-bit `get` refuses a FourBit-mode sequence
+        let case = packed::PackedDna::from_str_extended("ACGT").unwrap();
+        assert!(case.get(0).is_err());
+    }
 }
@@ -11,9 +11,12 @@
 // ```
 //
 // be sure to exit with informative error messages if the input is invalid
+//
+// it can also be run with `nuccount --file some.fasta` (or a .fastq file) to
+// sum nucleotide counts across every record in the file
 
-use dna::{packed::PackedDna, Nuc};
-use std::str::FromStr;
+use dna::{io, packed::PackedDna};
+use std::{fs, path::PathBuf, str::FromStr};
 use structopt::StructOpt;
 
 /// Count the number of occurrences of each nucleotide in the provided DNA.
@@ -22,46 +25,67 @@ struct Opts {
     /// The DNA sequence for which we should retrieve a nucleotide count.
     ///
     /// It is case insensitive but only nucleotides A, C, G and T are supported.
-    #[structopt(short = "d", long, required = true)]
-    dna: String,
+    #[structopt(short = "d", long, conflicts_with = "file")]
+    dna: Option<String>,
+
+    /// A FASTA/FASTQ file to sum nucleotide counts across, instead of a
+    /// single `--dna` string.
+    #[structopt(short = "f", long, parse(from_os_str))]
+    file: Option<PathBuf>,
 }
 
-fn main() {
-    // get input
-    let opts = Opts::from_args();
-    let dna = opts.dna;
-    println!("Input: {}", &dna);
-    let input = PackedDna::from_str(&dna);
-    // return if input contains illegal characters
-    if input.is_err() {
-        println!("unsupported input");
-        return;
+fn add_counts(packed_dna: &PackedDna, counts: &mut [usize; 4]) {
+    // built via `PackedDna::from_str`, so this is always 2-bit packed and never errors
+    let unit_counts = packed_dna.counts().expect("nuccount never builds extended-mode PackedDna");
+    for i in 0..4 {
+        counts[i] += unit_counts[i];
     }
-    let packed_dna = input.unwrap();
-    let length = packed_dna.len();
+}
 
-    // set counters for 4 types of nucleotides
-    let mut a_count: usize = 0;
-    let mut t_count: usize = 0;
-    let mut c_count: usize = 0;
-    let mut g_count: usize = 0;
+fn print_counts(counts: &[usize; 4]) {
+    println!("A: {}", counts[0]);
+    println!("C: {}", counts[1]);
+    println!("G: {}", counts[2]);
+    println!("T: {}", counts[3]);
+}
 
-    for i in 0..length {
-        let nuc = packed_dna.get(i);
-        if nuc.is_err() {
-            println!("fail to get nucleotide with index {}", i);
-            return;
-        }
-        match nuc.unwrap() {
-            Nuc::A => a_count += 1,
-            Nuc::C => c_count += 1,
-            Nuc::G => g_count += 1,
-            Nuc::T => t_count += 1,
+fn main() {
+    let opts = Opts::from_args();
+    let mut counts = [0usize; 4];
+
+    if let Some(path) = opts.file {
+        let contents = match fs::read_to_string(&path) {
+            Ok(contents) => contents,
+            Err(err) => {
+                println!("failed to read {}: {}", path.display(), err);
+                return;
+            }
+        };
+        let records = match io::parse_records(&contents) {
+            Ok(records) => records,
+            Err(err) => {
+                println!("failed to parse {}: {}", path.display(), err);
+                return;
+            }
+        };
+        println!("Input: {} ({} record(s))", path.display(), records.len());
+        for record in &records {
+            add_counts(&record.dna, &mut counts);
         }
+    } else if let Some(dna) = opts.dna {
+        println!("Input: {}", &dna);
+        let packed_dna = match PackedDna::from_str(&dna) {
+            Ok(packed_dna) => packed_dna,
+            Err(_) => {
+                println!("unsupported input");
+                return;
+            }
+        };
+        add_counts(&packed_dna, &mut counts);
+    } else {
+        println!("either --dna or --file must be provided");
+        return;
     }
-    // print counts
-    println!("A: {}", a_count);
-    println!("C: {}", c_count);
-    println!("G: {}", g_count);
-    println!("T: {}", t_count);
+
+    print_counts(&counts);
 }